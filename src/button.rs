@@ -6,6 +6,12 @@ use rwcommon::animation::Animated;
 use rwgfx::sprite::Sprite;
 use rwgfx::text::{Text, TextDescriptor};
 use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::keyboard::{Key, NamedKey};
+use winit::window::CursorIcon;
+
+use crate::button_event::{ButtonEvent, ButtonEventKind};
+use crate::drag_and_drop::DropTargetBounds;
+use crate::pending_mouse::normalise_scroll_delta;
 
 /// Collection of parameters for button creation.
 pub struct ButtonDescriptor<T> {
@@ -21,14 +27,14 @@ pub struct ButtonDescriptor<T> {
     pub texture_id: Option<u64>,
     /// Label of the button.
     pub label: Option<String>,
-    /// Optional callback called when the button is pressed.
-    pub on_press: Option<fn(&mut Button<T>, &mut T)>,
-    /// Optional callback called when the button is released.
-    pub on_release: Option<fn(&mut Button<T>, &mut T)>,
-    /// Optional callback called when the mouse enters the boundaries of the button.
-    pub on_enter: Option<fn(&mut Button<T>, &mut T)>,
-    /// Optional callback called when the mouse leaves the boundaries of the button.
-    pub on_exit: Option<fn(&mut Button<T>, &mut T)>,
+    /// Numeric code attached to every `ButtonEvent` this button emits.
+    pub code: u16,
+    /// Static tag attached to every `ButtonEvent` this button emits.
+    pub tag: &'static str,
+    /// Cursor icon to request from the OS while the button is hovered.
+    pub cursor: Option<CursorIcon>,
+    /// If true, this button can be picked up by the drag-and-drop manager once pressed.
+    pub draggable: bool,
 }
 
 /// Rectangular object that can be interacted with.
@@ -41,20 +47,28 @@ pub struct Button<T> {
     z_index: f32,
     /// If true, the mouse cursor is hovering over the button.
     hovered: bool,
-    /// If true, the user is clicking the button.
+    /// If true, the left mouse button was pressed over the button and is still held down, even if
+    /// the cursor has since left it.
     pressed: bool,
+    /// If true, Space/Enter is held down while the button holds keyboard focus. Tracked
+    /// separately from `pressed` so that releasing one input doesn't consume the other's press.
+    key_pressed: bool,
+    /// If true, this button currently holds keyboard focus.
+    focused: bool,
     /// Background colour of the button.
     back_colour: [f32; 4],
     /// Alpha value of the white overlay of the button (for hovered-pressed animations).
     overlay_alpha: Animated<f32>,
-    /// Optional callback called when the button is pressed.
-    on_press: Option<fn(&mut Button<T>, &mut T)>,
-    /// Optional callback called when the button is released.
-    on_release: Option<fn(&mut Button<T>, &mut T)>,
-    /// Optional callback called when the mouse enters the boundaries of the button.
-    on_enter: Option<fn(&mut Button<T>, &mut T)>,
-    /// Optional callback called when the mouse leaves the boundaries of the button.
-    on_exit: Option<fn(&mut Button<T>, &mut T)>,
+    /// Numeric code attached to every `ButtonEvent` this button emits.
+    code: u16,
+    /// Static tag attached to every `ButtonEvent` this button emits.
+    tag: &'static str,
+    /// Semantic events queued since the last drain.
+    events: Vec<ButtonEvent>,
+    /// Cursor icon to request from the OS while the button is hovered.
+    cursor: Option<CursorIcon>,
+    /// If true, this button can be picked up by the drag-and-drop manager once pressed.
+    draggable: bool,
     /// Label.
     label: Option<String>,
     /// Actual graphical component of the button.
@@ -64,11 +78,31 @@ pub struct Button<T> {
 }
 
 impl<T> Button<T> {
+    /// Queue a semantic event carrying this button's code and tag.
+    fn push_event(&mut self, kind: ButtonEventKind) {
+        self.events.push(ButtonEvent {
+            kind,
+            code: self.code,
+            tag: self.tag,
+        });
+    }
+
+    /// Drain the semantic events queued since the last call, for a central dispatcher to handle.
+    pub fn take_events(&mut self) -> Vec<ButtonEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     /// Process an event.
     /// If the event is directed at this button, true is returned to signal that the event was consumed.
     /// Otherwise, false is returned.
-    /// If the event is consumed, all relevant callbacks are called using the provided data.
-    pub fn consume_event(&mut self, data: &mut T, event: &WindowEvent) -> bool {
+    /// If the event is consumed, a corresponding `ButtonEvent` is queued; drain it with
+    /// `take_events`.
+    ///
+    /// `Pressed` fires on mouse/key down and `Released` on mouse/key up. This is the reverse of
+    /// the old `on_press`/`on_release` callbacks, which fired on mouse-up and mouse-down
+    /// respectively; that was a bug in the callback design, not an intended mapping, and callers
+    /// should update expectations accordingly.
+    pub fn consume_event(&mut self, event: &WindowEvent) -> bool {
         let mut event_consumed = false;
 
         match event {
@@ -87,9 +121,7 @@ impl<T> Button<T> {
                         self.hovered = true;
                         self.overlay_alpha
                             .set_target(self.overlay_alpha.target() + 0.1);
-                        if let Some(on_enter) = self.on_enter.as_ref() {
-                            on_enter(self, data);
-                        }
+                        self.push_event(ButtonEventKind::Entered);
                         event_consumed = true;
                     }
                 } else {
@@ -97,25 +129,20 @@ impl<T> Button<T> {
                         self.hovered = false;
                         self.overlay_alpha
                             .set_target(self.overlay_alpha.target() - 0.1);
-                        if let Some(on_exit) = self.on_exit.as_ref() {
-                            on_exit(self, data);
-                        }
+                        self.push_event(ButtonEventKind::Exited);
                         event_consumed = true;
                     }
                 }
             }
-            WindowEvent::MouseInput { state, button, .. } => {
-                // Only process the left mouse button.
-                if *button == MouseButton::Left {
+            WindowEvent::MouseInput { state, button, .. } => match *button {
+                MouseButton::Left => {
                     // If the button is already pressed, check for the mouse release.
                     if self.pressed {
                         if *state == ElementState::Released {
                             self.pressed = false;
                             self.overlay_alpha
                                 .set_target(self.overlay_alpha.target() - 0.1);
-                            if let Some(on_press) = self.on_press.as_ref() {
-                                on_press(self, data);
-                            }
+                            self.push_event(ButtonEventKind::Released);
                             event_consumed = true;
                         }
                     } else {
@@ -123,13 +150,60 @@ impl<T> Button<T> {
                             self.pressed = true;
                             self.overlay_alpha
                                 .set_target(self.overlay_alpha.target() + 0.1);
-                            if let Some(on_release) = self.on_release.as_ref() {
-                                on_release(self, data);
-                            }
+                            self.push_event(ButtonEventKind::Pressed);
                             event_consumed = true;
                         }
                     }
                 }
+                // Right and middle buttons are reported as discrete triggers, without the
+                // press/hold state tracking the left button gets, so they work as single-shot
+                // context-menu/scrollable-region signals.
+                MouseButton::Right if self.hovered => {
+                    self.push_event(match state {
+                        ElementState::Pressed => ButtonEventKind::PressedRight,
+                        ElementState::Released => ButtonEventKind::ReleasedRight,
+                    });
+                    event_consumed = true;
+                }
+                MouseButton::Middle if self.hovered => {
+                    self.push_event(match state {
+                        ElementState::Pressed => ButtonEventKind::PressedMiddle,
+                        ElementState::Released => ButtonEventKind::ReleasedMiddle,
+                    });
+                    event_consumed = true;
+                }
+                _ => (),
+            },
+            WindowEvent::MouseWheel { delta, .. } if self.hovered => {
+                self.push_event(ButtonEventKind::Scrolled(normalise_scroll_delta(*delta)));
+                event_consumed = true;
+            }
+            WindowEvent::KeyboardInput {
+                event: key_event, ..
+            } => {
+                // Space and Enter activate the button while it holds keyboard focus.
+                if self.focused
+                    && matches!(
+                        key_event.logical_key.as_ref(),
+                        Key::Named(NamedKey::Space) | Key::Named(NamedKey::Enter)
+                    )
+                {
+                    if self.key_pressed {
+                        if key_event.state == ElementState::Released {
+                            self.key_pressed = false;
+                            self.overlay_alpha
+                                .set_target(self.overlay_alpha.target() - 0.1);
+                            self.push_event(ButtonEventKind::Released);
+                            event_consumed = true;
+                        }
+                    } else if key_event.state == ElementState::Pressed {
+                        self.key_pressed = true;
+                        self.overlay_alpha
+                            .set_target(self.overlay_alpha.target() + 0.1);
+                        self.push_event(ButtonEventKind::Pressed);
+                        event_consumed = true;
+                    }
+                }
             }
             _ => (),
         }
@@ -137,6 +211,56 @@ impl<T> Button<T> {
         event_consumed
     }
 
+    /// Give or remove keyboard focus from this button, driving the same overlay highlight used
+    /// for hovering.
+    pub fn set_focused(&mut self, focused: bool) {
+        if focused == self.focused {
+            return;
+        }
+        self.focused = focused;
+        if focused {
+            self.overlay_alpha
+                .set_target(self.overlay_alpha.target() + 0.1);
+        } else {
+            self.overlay_alpha
+                .set_target(self.overlay_alpha.target() - 0.1);
+        }
+    }
+
+    /// Whether this button currently holds keyboard focus.
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Cursor icon this button wants shown, if any.
+    /// Returns `None` while the mouse is not hovering it, so that callers can fall back to the
+    /// default arrow or defer to another widget.
+    pub fn requested_cursor(&self) -> Option<CursorIcon> {
+        if self.hovered {
+            self.cursor
+        } else {
+            None
+        }
+    }
+
+    /// Z-index of the button, for arbitrating between overlapping widgets.
+    pub fn z_index(&self) -> f32 {
+        self.z_index
+    }
+
+    /// Whether this button can be picked up by the drag-and-drop manager.
+    pub fn draggable(&self) -> bool {
+        self.draggable
+    }
+
+    /// Bounds of this button, for registering it as a drag-and-drop target.
+    pub fn bounds(&self) -> DropTargetBounds {
+        DropTargetBounds {
+            position: *self.position.current(),
+            size: *self.size.current(),
+        }
+    }
+
     /// Draw the button.
     pub fn draw<'a>(
         &'a self,
@@ -184,12 +308,15 @@ impl<T> Button<T> {
             z_index: descriptor.z_index,
             hovered: false,
             pressed: false,
+            key_pressed: false,
+            focused: false,
             back_colour: descriptor.back_colour,
             overlay_alpha: Animated::new(0.0, Duration::milliseconds(100)),
-            on_press: descriptor.on_press,
-            on_release: descriptor.on_release,
-            on_enter: descriptor.on_enter,
-            on_exit: descriptor.on_exit,
+            code: descriptor.code,
+            tag: descriptor.tag,
+            events: Vec::new(),
+            cursor: descriptor.cursor,
+            draggable: descriptor.draggable,
             label: descriptor.label.clone(),
             sprite,
             text,
@@ -0,0 +1,39 @@
+//! Semantic events emitted by interactive widgets.
+
+/// Kind of interaction being reported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ButtonEventKind {
+    /// The left mouse button went down over it, or Space/Enter was pressed while focused.
+    Pressed,
+    /// The left mouse button, pressed over it, went up (wherever the cursor now is), or
+    /// Space/Enter was released while focused.
+    Released,
+    /// The mouse entered the button's bounds.
+    Entered,
+    /// The mouse left the button's bounds.
+    Exited,
+    /// The right mouse button went down while hovering it.
+    PressedRight,
+    /// The right mouse button went up while hovering it.
+    ReleasedRight,
+    /// The middle mouse button went down while hovering it.
+    PressedMiddle,
+    /// The middle mouse button went up while hovering it.
+    ReleasedMiddle,
+    /// The mouse wheel was scrolled while hovering it, carrying the normalised scroll amount in
+    /// lines.
+    Scrolled(f32),
+}
+
+/// A semantic event emitted by a `Button`, decoupling widgets from their handlers. Every event
+/// carries the `code` and `tag` set on the button's descriptor, so a single central dispatcher
+/// can react to many widgets, or log/replay UI intent, without per-widget function pointers.
+#[derive(Debug, Clone, Copy)]
+pub struct ButtonEvent {
+    /// What happened.
+    pub kind: ButtonEventKind,
+    /// Caller-defined numeric code identifying the button, set on its descriptor.
+    pub code: u16,
+    /// Caller-defined static tag identifying the button, set on its descriptor.
+    pub tag: &'static str,
+}
@@ -0,0 +1,159 @@
+//! Cross-widget drag-and-drop support.
+
+use cgmath::{Point2, Vector2};
+use std::any::Any;
+
+/// Minimum cursor travel, in screen units, before a press turns into a drag.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// Axis-aligned bounds of a drop target, in screen coordinates.
+#[derive(Debug, Clone, Copy)]
+pub struct DropTargetBounds {
+    /// Top-left corner of the target.
+    pub position: Point2<f32>,
+    /// Size of the target.
+    pub size: Vector2<f32>,
+}
+
+impl DropTargetBounds {
+    /// Whether the given point lies within these bounds.
+    pub fn contains(&self, point: Point2<f32>) -> bool {
+        self.position.x <= point.x
+            && point.x <= self.position.x + self.size.x
+            && self.position.y <= point.y
+            && point.y <= self.position.y + self.size.y
+    }
+}
+
+/// Renders the floating drag image at the current cursor position.
+pub type DragRenderFn =
+    for<'a> fn(&Point2<f32>, &dyn Any, &mut rwgfx::RenderPass<'a>, &rwgfx::renderer::FrameContext<'a>);
+
+/// A drag in progress: a payload captured on press, followed across the screen until released.
+struct DragInProgress {
+    /// Current cursor position, updated every frame while the drag is active.
+    position: Point2<f32>,
+    /// Arbitrary data carried by the drag, delivered to whichever target accepts it.
+    payload: Box<dyn Any>,
+    /// Renders the floating drag image.
+    render: DragRenderFn,
+}
+
+/// A registered drop target: its bounds, plus the callback invoked with the payload when a drag
+/// is released over it.
+struct DropTarget<T> {
+    /// Bounds checked against the cursor on release.
+    bounds: DropTargetBounds,
+    /// Called with the payload when a drag is released inside `bounds`.
+    on_drop: fn(&mut T, &dyn Any),
+}
+
+/// Cross-cutting drag-and-drop manager, owned by `WindowApp`. Tracks at most one drag in
+/// progress: begun when a draggable widget is pressed and the cursor moves past a small
+/// threshold, followed across the screen every frame, and resolved against registered drop
+/// targets on release.
+pub struct DragAndDrop<T> {
+    /// Position of the press that may turn into a drag, if any.
+    press_origin: Option<Point2<f32>>,
+    /// The drag currently in progress, once the threshold has been crossed.
+    active: Option<DragInProgress>,
+    /// Registered drop targets, checked against the cursor on release.
+    targets: Vec<DropTarget<T>>,
+}
+
+impl<T> DragAndDrop<T> {
+    /// Create an empty drag-and-drop manager.
+    pub fn new() -> Self {
+        Self {
+            press_origin: None,
+            active: None,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Register a drop target. `on_drop` only gets `&mut T`, not the widget the bounds came from,
+    /// since target bounds can be registered without keeping a matching widget reference around;
+    /// callers needing to act on a specific widget should identify it from `T` itself (e.g. an id
+    /// or index captured by the callback). Targets accumulate across frames; call `clear_targets`
+    /// first if the layout can change.
+    pub fn register_target(&mut self, bounds: DropTargetBounds, on_drop: fn(&mut T, &dyn Any)) {
+        self.targets.push(DropTarget { bounds, on_drop });
+    }
+
+    /// Remove all registered drop targets.
+    pub fn clear_targets(&mut self) {
+        self.targets.clear();
+    }
+
+    /// Record a press that may turn into a drag once the cursor moves far enough.
+    pub fn begin_press(&mut self, position: Point2<f32>) {
+        self.press_origin = Some(position);
+    }
+
+    /// Feed a cursor move. If a press is pending and the threshold is crossed, `start_drag` is
+    /// called to capture the payload and a drag begins; if a drag is already active, it follows
+    /// the cursor instead.
+    pub fn update_position(
+        &mut self,
+        position: Point2<f32>,
+        start_drag: impl FnOnce() -> (Box<dyn Any>, DragRenderFn),
+    ) {
+        if let Some(drag) = self.active.as_mut() {
+            drag.position = position;
+            return;
+        }
+
+        let Some(origin) = self.press_origin else {
+            return;
+        };
+
+        let delta = Vector2::new(position.x - origin.x, position.y - origin.y);
+        if (delta.x * delta.x + delta.y * delta.y).sqrt() >= DRAG_THRESHOLD {
+            let (payload, render) = start_drag();
+            self.active = Some(DragInProgress {
+                position,
+                payload,
+                render,
+            });
+        }
+    }
+
+    /// Release the drag, if any, delivering its payload to whichever registered drop target
+    /// contains the cursor.
+    pub fn release(&mut self, data: &mut T) {
+        self.press_origin = None;
+        let Some(drag) = self.active.take() else {
+            return;
+        };
+
+        if let Some(target) = self
+            .targets
+            .iter()
+            .find(|target| target.bounds.contains(drag.position))
+        {
+            (target.on_drop)(data, drag.payload.as_ref());
+        }
+    }
+
+    /// Whether a drag is currently in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Draw the floating drag image, if a drag is in progress.
+    pub fn draw<'a>(
+        &'a self,
+        render_pass: &mut rwgfx::RenderPass<'a>,
+        frame_context: &rwgfx::renderer::FrameContext<'a>,
+    ) {
+        if let Some(drag) = &self.active {
+            (drag.render)(&drag.position, drag.payload.as_ref(), render_pass, frame_context);
+        }
+    }
+}
+
+impl<T> Default for DragAndDrop<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
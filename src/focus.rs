@@ -0,0 +1,59 @@
+//! Keyboard focus order management.
+
+/// Tracks which widget, out of a user-defined focus order, currently holds keyboard focus.
+/// `WindowApp` owns one of these and advances/retreats it on Tab / Shift-Tab; it is up to the
+/// caller to assign each focusable widget an index within `0..count` and keep it in sync with
+/// `current()`.
+#[derive(Debug, Default)]
+pub struct FocusOrder {
+    /// Number of focusable widgets currently registered.
+    count: usize,
+    /// Index of the focused widget within that order, if any.
+    current: Option<usize>,
+}
+
+impl FocusOrder {
+    /// Create an empty focus order.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how many focusable widgets exist, clamping the current focus if it no longer fits.
+    pub fn set_count(&mut self, count: usize) {
+        self.count = count;
+        if let Some(current) = self.current {
+            if current >= count {
+                self.current = count.checked_sub(1);
+            }
+        }
+    }
+
+    /// Index of the currently focused widget, if any.
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Move focus to the next widget, wrapping around to the first.
+    pub fn advance(&mut self) {
+        if self.count == 0 {
+            self.current = None;
+            return;
+        }
+        self.current = Some(match self.current {
+            Some(current) => (current + 1) % self.count,
+            None => 0,
+        });
+    }
+
+    /// Move focus to the previous widget, wrapping around to the last.
+    pub fn retreat(&mut self) {
+        if self.count == 0 {
+            self.current = None;
+            return;
+        }
+        self.current = Some(match self.current {
+            Some(0) | None => self.count - 1,
+            Some(current) => current - 1,
+        });
+    }
+}
@@ -0,0 +1,107 @@
+//! Buffering of raw mouse activity between redraws.
+
+use glium::winit::dpi::PhysicalPosition;
+use glium::winit::event::{DeviceId, ElementState, MouseButton, MouseScrollDelta};
+
+/// A discrete button press or release queued for the next flush.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PendingButtonEvent {
+    /// Device that reported the button event.
+    pub device_id: DeviceId,
+    /// Button that changed state.
+    pub button: MouseButton,
+    /// New state of the button.
+    pub state: ElementState,
+}
+
+/// One piece of mouse activity buffered since the last flush, in the order it was received.
+pub(crate) enum PendingEvent {
+    /// A cursor move. Consecutive motions coalesce into the latest position.
+    Motion {
+        device_id: DeviceId,
+        position: PhysicalPosition<f64>,
+    },
+    /// A discrete button press/release.
+    Button(PendingButtonEvent),
+    /// Scroll delta, normalised to lines. Consecutive scrolls accumulate.
+    Scroll { device_id: DeviceId, lines: f32 },
+}
+
+/// Normalise a scroll delta into scroll lines, regardless of whether the platform reports lines
+/// or pixels.
+pub(crate) fn normalise_scroll_delta(delta: MouseScrollDelta) -> f32 {
+    match delta {
+        MouseScrollDelta::LineDelta(_, y) => y,
+        MouseScrollDelta::PixelDelta(position) => position.y as f32 / 120.0,
+    }
+}
+
+/// Buffers raw mouse activity received between two redraws, so that a flood of motion events
+/// collapses into a single move per frame. Activity is kept in a single ordered queue rather than
+/// one slot per event kind, so that e.g. a move landing between two button presses is still
+/// replayed in between them rather than jumping to the front or back of the batch.
+#[derive(Default)]
+pub(crate) struct PendingMouse {
+    queue: Vec<PendingEvent>,
+}
+
+impl PendingMouse {
+    /// Queue a cursor motion. If the most recently queued entry is also a motion, it is replaced
+    /// in place rather than appended, so a flood of moves still collapses to one per frame.
+    /// Returns `true` if an existing motion entry was replaced this way.
+    pub(crate) fn queue_motion(
+        &mut self,
+        device_id: DeviceId,
+        position: PhysicalPosition<f64>,
+    ) -> bool {
+        if let Some(PendingEvent::Motion {
+            device_id: last_device_id,
+            position: last_position,
+        }) = self.queue.last_mut()
+        {
+            *last_device_id = device_id;
+            *last_position = position;
+            return true;
+        }
+
+        self.queue.push(PendingEvent::Motion { device_id, position });
+        false
+    }
+
+    /// Queue a discrete button press/release. Every button event is kept, in order.
+    pub(crate) fn queue_button(
+        &mut self,
+        device_id: DeviceId,
+        button: MouseButton,
+        state: ElementState,
+    ) {
+        self.queue.push(PendingEvent::Button(PendingButtonEvent {
+            device_id,
+            button,
+            state,
+        }));
+    }
+
+    /// Queue a scroll delta, normalising it to lines first. If the most recently queued entry is
+    /// also a scroll, the deltas accumulate instead of producing two separate wheel events.
+    pub(crate) fn queue_scroll(&mut self, device_id: DeviceId, delta: MouseScrollDelta) {
+        let lines = normalise_scroll_delta(delta);
+
+        if let Some(PendingEvent::Scroll {
+            device_id: last_device_id,
+            lines: last_lines,
+        }) = self.queue.last_mut()
+        {
+            *last_device_id = device_id;
+            *last_lines += lines;
+            return;
+        }
+
+        self.queue.push(PendingEvent::Scroll { device_id, lines });
+    }
+
+    /// Take the buffered activity, clearing it, in the order it was received.
+    pub(crate) fn take(&mut self) -> Vec<PendingEvent> {
+        std::mem::take(&mut self.queue)
+    }
+}
@@ -1,31 +1,49 @@
 //! A graphical window in the operating system.
 
+use crate::button_event::ButtonEvent;
+pub use crate::drag_and_drop::DragAndDrop;
 pub use crate::error::WindowCreationError;
+pub use crate::focus::FocusOrder;
+use crate::pending_mouse::{PendingEvent, PendingMouse};
 use glium::winit::application::ApplicationHandler;
-use glium::winit::event::WindowEvent;
-use glium::winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
-use glium::winit::window::WindowId;
+use glium::winit::event::{ElementState, MouseScrollDelta, TouchPhase, WindowEvent};
+use glium::winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy};
+use glium::winit::keyboard::{Key, ModifiersState, NamedKey};
+use glium::winit::window::{CursorIcon, WindowId};
 use rwgfx::renderer::{Renderer, RendererDescriptor};
 use rwlog::sender::Logger;
 
 /// Data required for creating a window application.
-pub struct WindowAppDescriptor<T> {
+pub struct WindowAppDescriptor<T, E = ()> {
     /// Logger
     pub logger: Logger,
     /// Data needed for the user to customise the application's behaviour..
     pub data: T,
     /// Function called before new events are processed.
-    pub on_before_events: Option<fn(&mut WindowApp<T>)>,
+    pub on_before_events: Option<fn(&mut WindowApp<T, E>)>,
     /// Function called after new events are processed.
-    pub on_after_events: Option<fn(&mut WindowApp<T>)>,
+    pub on_after_events: Option<fn(&mut WindowApp<T, E>)>,
     /// Function called before drawing each frame.
-    pub on_before_draw: Option<fn(&mut WindowApp<T>)>,
+    pub on_before_draw: Option<fn(&mut WindowApp<T, E>)>,
     /// Function called after drawing each frame.
-    pub on_after_draw: Option<fn(&mut WindowApp<T>)>,
+    pub on_after_draw: Option<fn(&mut WindowApp<T, E>)>,
+    /// Function called with window events, so that widgets owned by `data` can consume them.
+    /// Mouse moves, buttons and scrolls are buffered and passed on once per frame, just before
+    /// drawing, with consecutive moves and scrolls merged. Close requests, modifier changes, Tab
+    /// presses and redraw requests are handled by the window itself and not passed on.
+    pub on_event: Option<fn(&mut WindowApp<T, E>, &WindowEvent)>,
+    /// Function called after event dispatch to determine which cursor icon should currently be
+    /// shown. Returning `None` falls back to the default arrow.
+    pub on_cursor_request: Option<fn(&mut WindowApp<T, E>) -> Option<CursorIcon>>,
+    /// Function called when a custom user event, sent through an `EventLoopProxy`, is received.
+    pub on_user_event: Option<fn(&mut WindowApp<T, E>, E)>,
+    /// Single handler for semantic `ButtonEvent`s drained from widgets, so that logging/replay of
+    /// UI intent can happen in one place instead of per-widget callbacks.
+    pub on_button_event: Option<fn(&mut WindowApp<T, E>, ButtonEvent)>,
 }
 
 /// Application with a graphical window.
-pub struct WindowApp<T> {
+pub struct WindowApp<T, E = ()> {
     /// Logger
     logger: Logger,
     /// Renderer.
@@ -35,16 +53,37 @@ pub struct WindowApp<T> {
     /// Data needed for the user to customise the application's behaviour..
     data: T,
     /// Function called before new events are processed.
-    on_before_events: Option<fn(&mut WindowApp<T>)>,
+    on_before_events: Option<fn(&mut WindowApp<T, E>)>,
     /// Function called after new events are processed.
-    on_after_events: Option<fn(&mut WindowApp<T>)>,
+    on_after_events: Option<fn(&mut WindowApp<T, E>)>,
     /// Function called before drawing each frame.
-    on_before_draw: Option<fn(&mut WindowApp<T>)>,
+    on_before_draw: Option<fn(&mut WindowApp<T, E>)>,
     /// Function called after drawing each frame.
-    on_after_draw: Option<fn(&mut WindowApp<T>)>,
+    on_after_draw: Option<fn(&mut WindowApp<T, E>)>,
+    /// Function called with window events, so that widgets owned by `data` can consume them.
+    /// Mouse activity reaches it only through the once-per-frame flush of `pending_mouse`.
+    on_event: Option<fn(&mut WindowApp<T, E>, &WindowEvent)>,
+    /// Function called after event dispatch to determine which cursor icon should currently be
+    /// shown. Returning `None` falls back to the default arrow.
+    on_cursor_request: Option<fn(&mut WindowApp<T, E>) -> Option<CursorIcon>>,
+    /// Function called when a custom user event, sent through an `EventLoopProxy`, is received.
+    on_user_event: Option<fn(&mut WindowApp<T, E>, E)>,
+    /// Single handler for semantic `ButtonEvent`s drained from widgets, so that logging/replay of
+    /// UI intent can happen in one place instead of per-widget callbacks.
+    on_button_event: Option<fn(&mut WindowApp<T, E>, ButtonEvent)>,
+    /// Raw mouse activity buffered since the last flush, dispatched once per frame.
+    pending_mouse: PendingMouse,
+    /// Keyboard focus order, advanced by Tab and retreated by Shift-Tab.
+    focus: FocusOrder,
+    /// Current keyboard modifier state, tracked to distinguish Tab from Shift-Tab.
+    modifiers: ModifiersState,
+    /// Proxy used to send custom user events into this window's event loop from other threads.
+    proxy: EventLoopProxy<E>,
+    /// Drag-and-drop manager, tracking any drag currently in progress.
+    drag_and_drop: DragAndDrop<T>,
 }
 
-impl<T> WindowApp<T> {
+impl<T, E> WindowApp<T, E> {
     /// Get a mutable reference to the user data.
     pub fn data(&mut self) -> &mut T {
         &mut self.data
@@ -68,15 +107,93 @@ impl<T> WindowApp<T> {
         &mut self.renderer
     }
 
+    /// Get the keyboard focus order, so the caller can register how many focusable widgets exist
+    /// and read back which one should currently be drawn as focused.
+    pub fn focus(&mut self) -> &mut FocusOrder {
+        &mut self.focus
+    }
+
+    /// Get a proxy that can be used to send custom user events into this window's event loop,
+    /// from this or any other thread.
+    pub fn event_loop_proxy(&self) -> EventLoopProxy<E> {
+        self.proxy.clone()
+    }
+
+    /// Get the drag-and-drop manager, so widgets can register as drop targets and the caller can
+    /// drive drag lifecycle from mouse events.
+    pub fn drag_and_drop(&mut self) -> &mut DragAndDrop<T> {
+        &mut self.drag_and_drop
+    }
+
+    /// Run `f` with simultaneous access to the drag-and-drop manager and the user data, for the
+    /// calls (`DragAndDrop::release`, `DragAndDrop::update_position`) that need both at once.
+    /// Splitting the borrow here, rather than through two separate `&mut self` accessors, is what
+    /// lets callers actually reach them together.
+    pub fn with_drag_and_drop<R>(&mut self, f: impl FnOnce(&mut DragAndDrop<T>, &mut T) -> R) -> R {
+        f(&mut self.drag_and_drop, &mut self.data)
+    }
+
+    /// Hand a semantic `ButtonEvent`, drained from a widget, to the single registered handler.
+    pub fn dispatch_button_event(&mut self, event: ButtonEvent) {
+        if let Some(fun) = self.on_button_event {
+            fun(self, event);
+        }
+    }
+
+    /// Dispatch buffered mouse activity in the order it was originally received, so a move that
+    /// happened between two button events (e.g. enter, press, leave) still replays in between
+    /// them rather than being reordered to the front or back of the batch. Called once per
+    /// frame, just before `RedrawRequested` draws.
+    fn flush_pending_mouse(&mut self) {
+        let pending = self.pending_mouse.take();
+
+        let Some(fun) = self.on_event else {
+            return;
+        };
+
+        for entry in pending {
+            match entry {
+                PendingEvent::Motion {
+                    device_id,
+                    position,
+                } => {
+                    fun(self, &WindowEvent::CursorMoved { device_id, position });
+                }
+                PendingEvent::Button(button) => {
+                    fun(
+                        self,
+                        &WindowEvent::MouseInput {
+                            device_id: button.device_id,
+                            state: button.state,
+                            button: button.button,
+                        },
+                    );
+                }
+                PendingEvent::Scroll { device_id, lines } => {
+                    fun(
+                        self,
+                        &WindowEvent::MouseWheel {
+                            device_id,
+                            delta: MouseScrollDelta::LineDelta(0.0, lines),
+                            phase: TouchPhase::Moved,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
     /// Create a new window.
     pub fn new(
-        app_descriptor: WindowAppDescriptor<T>,
-    ) -> Result<(EventLoop<()>, WindowApp<T>), WindowCreationError> {
-        let event_loop = glium::winit::event_loop::EventLoop::builder()
+        app_descriptor: WindowAppDescriptor<T, E>,
+    ) -> Result<(EventLoop<E>, WindowApp<T, E>), WindowCreationError> {
+        let event_loop = glium::winit::event_loop::EventLoop::<E>::with_user_event()
             .build()
             .map_err(|_| WindowCreationError::EventLoopCreation)?;
         event_loop.set_control_flow(ControlFlow::Poll);
 
+        let proxy = event_loop.create_proxy();
+
         let (window, display) =
             glium::backend::glutin::SimpleWindowBuilder::new().build(&event_loop);
 
@@ -103,28 +220,84 @@ impl<T> WindowApp<T> {
                 on_after_events: app_descriptor.on_after_events,
                 on_before_draw: app_descriptor.on_before_draw,
                 on_after_draw: app_descriptor.on_after_draw,
+                on_event: app_descriptor.on_event,
+                on_cursor_request: app_descriptor.on_cursor_request,
+                on_user_event: app_descriptor.on_user_event,
+                on_button_event: app_descriptor.on_button_event,
+                pending_mouse: PendingMouse::default(),
+                focus: FocusOrder::new(),
+                modifiers: ModifiersState::default(),
+                proxy,
+                drag_and_drop: DragAndDrop::new(),
             },
         ))
     }
 }
 
-impl<T> ApplicationHandler for WindowApp<T> {
+impl<T, E> ApplicationHandler<E> for WindowApp<T, E> {
     fn resumed(&mut self, _event_loop: &ActiveEventLoop) {}
 
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: E) {
+        if let Some(fun) = self.on_user_event {
+            fun(self, event);
+        }
+    }
+
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
         if let Some(fun) = self.on_before_events {
             fun(self);
         }
 
-        match event {
+        match &event {
             WindowEvent::CloseRequested => {
                 rwlog::info!(&self.logger, "Goodbye!");
                 event_loop.exit();
             }
+            WindowEvent::CursorMoved {
+                device_id,
+                position,
+            } => {
+                self.pending_mouse.queue_motion(*device_id, *position);
+            }
+            WindowEvent::MouseInput {
+                device_id,
+                state,
+                button,
+            } => {
+                self.pending_mouse.queue_button(*device_id, *button, *state);
+            }
+            WindowEvent::MouseWheel {
+                device_id, delta, ..
+            } => {
+                self.pending_mouse.queue_scroll(*device_id, *delta);
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
+            }
+            WindowEvent::KeyboardInput { event, .. }
+                if event.state == ElementState::Pressed
+                    && event.logical_key == Key::Named(NamedKey::Tab) =>
+            {
+                if self.modifiers.shift_key() {
+                    self.focus.retreat();
+                } else {
+                    self.focus.advance();
+                }
+            }
             WindowEvent::RedrawRequested => {
+                self.flush_pending_mouse();
                 self.draw();
             }
-            _ => (),
+            _ => {
+                if let Some(fun) = self.on_event {
+                    fun(self, &event);
+                }
+            }
+        }
+
+        if let Some(fun) = self.on_cursor_request {
+            let icon = fun(self).unwrap_or(CursorIcon::Default);
+            self.window.set_cursor(icon);
         }
 
         self.window.request_redraw();